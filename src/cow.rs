@@ -0,0 +1,203 @@
+//! A borrowed-or-shared string that stays two words wide.
+//!
+//! `CowRcStr` is the `RcStr` counterpart for callers who mostly deal with
+//! borrowed slices into some other buffer, and only occasionally need to
+//! keep a string around past the buffer's lifetime. Rather than wrapping a
+//! `Cow<str>` (which is as large as its largest variant, `String`, i.e.
+//! three words), it packs a pointer and a length/flag into exactly two
+//! words by stealing the value `usize::MAX` from the borrowed length to
+//! mark the owned, ref-counted case.
+
+use alloc::borrow::Cow;
+use alloc::rc::Rc;
+use alloc::string::String;
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::mem;
+use core::ops::Deref;
+use core::ptr::NonNull;
+use core::slice;
+use core::str;
+
+use crate::RcStr;
+
+/// A string that is either borrowed for `'a`, or a ref-counted, owned
+/// string shared the same way as `RcStr`.
+///
+/// # Example
+/// ```
+/// use rcstr::CowRcStr;
+/// let a: CowRcStr = "foo".into();
+/// let b: CowRcStr = String::from("foo").into();
+/// assert_eq!(a, b);
+/// ```
+pub struct CowRcStr<'a> {
+	ptr: NonNull<()>,
+	borrowed_len_or_max: usize,
+	phantom: PhantomData<&'a str>,
+}
+
+impl<'a> CowRcStr<'a> {
+	/// Wrap a borrowed string slice without allocating.
+	pub fn from_borrowed(value: &'a str) -> CowRcStr<'a> {
+		assert!(
+			value.len() < usize::MAX,
+			"borrowed string is too long to be represented"
+		);
+		CowRcStr {
+			ptr: unsafe { NonNull::new_unchecked(value.as_ptr() as *mut ()) },
+			borrowed_len_or_max: value.len(),
+			phantom: PhantomData,
+		}
+	}
+
+	fn from_rc(rc: Rc<String>) -> CowRcStr<'static> {
+		let ptr = Rc::into_raw(rc) as *mut ();
+		CowRcStr {
+			ptr: unsafe { NonNull::new_unchecked(ptr) },
+			borrowed_len_or_max: usize::MAX,
+			phantom: PhantomData,
+		}
+	}
+
+	fn is_borrowed(&self) -> bool {
+		self.borrowed_len_or_max != usize::MAX
+	}
+
+	fn as_str(&self) -> &str {
+		if self.is_borrowed() {
+			unsafe {
+				let slice = slice::from_raw_parts(self.ptr.as_ptr() as *const u8, self.borrowed_len_or_max);
+				str::from_utf8_unchecked(slice)
+			}
+		} else {
+			unsafe { &*(self.ptr.as_ptr() as *const String) }
+		}
+	}
+
+	/// Consume the pointer without running `Drop`, handing the raw parts to
+	/// the caller. Used by conversions that want to reuse an existing `Rc`
+	/// instead of bumping and then immediately dropping its refcount.
+	fn into_raw_parts(self) -> (NonNull<()>, usize) {
+		let parts = (self.ptr, self.borrowed_len_or_max);
+		mem::forget(self);
+		parts
+	}
+}
+
+impl<'a> Clone for CowRcStr<'a> {
+	fn clone(&self) -> CowRcStr<'a> {
+		if !self.is_borrowed() {
+			unsafe {
+				Rc::<String>::increment_strong_count(self.ptr.as_ptr() as *const String);
+			}
+		}
+		CowRcStr {
+			ptr: self.ptr,
+			borrowed_len_or_max: self.borrowed_len_or_max,
+			phantom: PhantomData,
+		}
+	}
+}
+
+impl<'a> Drop for CowRcStr<'a> {
+	fn drop(&mut self) {
+		if !self.is_borrowed() {
+			unsafe {
+				drop(Rc::from_raw(self.ptr.as_ptr() as *const String));
+			}
+		}
+	}
+}
+
+impl<'a> From<&'a str> for CowRcStr<'a> {
+	fn from(value: &'a str) -> CowRcStr<'a> {
+		CowRcStr::from_borrowed(value)
+	}
+}
+
+impl<'a> From<String> for CowRcStr<'a> {
+	fn from(value: String) -> CowRcStr<'a> {
+		CowRcStr::from_rc(Rc::new(value))
+	}
+}
+
+impl<'a> From<Cow<'a, str>> for CowRcStr<'a> {
+	fn from(value: Cow<'a, str>) -> CowRcStr<'a> {
+		match value {
+			Cow::Borrowed(s) => CowRcStr::from_borrowed(s),
+			Cow::Owned(s) => CowRcStr::from(s),
+		}
+	}
+}
+
+impl<'a> From<CowRcStr<'a>> for RcStr {
+	fn from(value: CowRcStr<'a>) -> RcStr {
+		if value.is_borrowed() {
+			RcStr::new(value.as_str())
+		} else {
+			let (ptr, _) = value.into_raw_parts();
+			RcStr::from_rc(unsafe { Rc::from_raw(ptr.as_ptr() as *const String) })
+		}
+	}
+}
+
+impl<'a> PartialEq for CowRcStr<'a> {
+	fn eq(&self, other: &CowRcStr<'a>) -> bool {
+		self.as_str() == other.as_str()
+	}
+}
+
+impl<'a> Eq for CowRcStr<'a> {}
+
+impl<'a> PartialOrd for CowRcStr<'a> {
+	fn partial_cmp(&self, other: &CowRcStr<'a>) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+impl<'a> Ord for CowRcStr<'a> {
+	fn cmp(&self, other: &CowRcStr<'a>) -> Ordering {
+		self.as_str().cmp(other.as_str())
+	}
+}
+
+impl<'a> Hash for CowRcStr<'a> {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		self.as_str().hash(state)
+	}
+}
+
+impl<'a> fmt::Debug for CowRcStr<'a> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		self.as_str().fmt(f)
+	}
+}
+
+impl<'a> fmt::Display for CowRcStr<'a> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		self.as_str().fmt(f)
+	}
+}
+
+impl<'a> Borrow<str> for CowRcStr<'a> {
+	fn borrow(&self) -> &str {
+		self.as_str()
+	}
+}
+
+impl<'a> Deref for CowRcStr<'a> {
+	type Target = str;
+	fn deref(&self) -> &str {
+		self.as_str()
+	}
+}
+
+impl<'a> AsRef<str> for CowRcStr<'a> {
+	fn as_ref(&self) -> &str {
+		self.as_str()
+	}
+}
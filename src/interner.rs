@@ -0,0 +1,73 @@
+//! A simple string interner built on top of `RcStr`.
+
+use std::collections::HashSet;
+
+use crate::RcStr;
+
+/// Deduplicates strings, handing out a cheaply-clonable `RcStr` for each
+/// distinct value interned.
+///
+/// # Example
+/// ```
+/// use rcstr::Interner;
+/// let mut interner = Interner::new();
+/// let a = interner.intern("foo");
+/// let b = interner.intern("foo");
+/// assert_eq!(a, b);
+/// assert_eq!(interner.len(), 1);
+/// ```
+#[derive(Default)]
+pub struct Interner {
+	strings: HashSet<RcStr>,
+}
+
+impl Interner {
+	/// Create a new, empty interner.
+	pub fn new() -> Interner {
+		Interner {
+			strings: HashSet::new(),
+		}
+	}
+
+	/// Return the `RcStr` for `s`, allocating and storing a new one only if
+	/// `s` has not been interned yet.
+	pub fn intern(&mut self, s: &str) -> RcStr {
+		if let Some(existing) = self.strings.get(s) {
+			return existing.clone();
+		}
+		let value = RcStr::new(s);
+		self.strings.insert(value.clone());
+		value
+	}
+
+	/// Like [`intern`](Interner::intern), but reuses the allocation in `s`
+	/// when it turns out to be a new string.
+	pub fn intern_owned(&mut self, s: String) -> RcStr {
+		if let Some(existing) = self.strings.get(s.as_str()) {
+			return existing.clone();
+		}
+		let value = RcStr::new(s);
+		self.strings.insert(value.clone());
+		value
+	}
+
+	/// Number of distinct strings currently interned.
+	pub fn len(&self) -> usize {
+		self.strings.len()
+	}
+
+	/// Check whether no strings have been interned yet.
+	pub fn is_empty(&self) -> bool {
+		self.strings.is_empty()
+	}
+
+	/// Check whether `s` has already been interned.
+	pub fn contains(&self, s: &str) -> bool {
+		self.strings.contains(s)
+	}
+
+	/// Forget all interned strings.
+	pub fn clear(&mut self) {
+		self.strings.clear()
+	}
+}
@@ -3,8 +3,15 @@
 //! A reference counted string that acts like a regular str slice, hiding the
 //! fact that it is wrapped in `Rc`.
 //!
+//! `RcStr` only needs `alloc`; the crate is `#![no_std]` whenever the `std`
+//! feature is disabled. The default `std` feature additionally pulls in
+//! convenience impls and examples that rely on `std::collections`; disable
+//! default features to use `RcStr` with just an allocator.
+//!
 //! # Example
 //! ```
+//! # #[cfg(feature = "std")]
+//! # fn main() {
 //! use rcstr::RcStr;
 //! use std::collections::HashSet;
 //!
@@ -14,16 +21,55 @@
 //! assert!(map.contains("foo"));
 //! assert!(map.contains(&RcStr::new("foo")));
 //! assert!(!map.contains("bar"));
+//! # }
+//! # #[cfg(not(feature = "std"))]
+//! # fn main() {}
 //! ```
 
-use std::borrow::Borrow;
-use std::cmp::Ordering;
-use std::fmt;
-use std::ops::Deref;
-use std::rc::Rc;
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::rc::Rc;
+use alloc::string::String;
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::Deref;
+
+mod cow;
 
-#[derive(Clone, Hash, PartialEq, PartialOrd)]
-pub struct RcStr(Rc<String>);
+pub use cow::CowRcStr;
+
+#[cfg(feature = "std")]
+mod interner;
+
+#[cfg(feature = "std")]
+pub use interner::Interner;
+
+/// The internal representation of an `RcStr`.
+///
+/// Literals and other strings that live for the whole program are stored as
+/// `Static` and never allocate or take part in ref-counting; everything else
+/// goes through the `Rc` path, same as before.
+#[derive(Clone)]
+enum Repr {
+	Rc(Rc<String>),
+	Static(&'static str),
+}
+
+impl Repr {
+	fn as_str(&self) -> &str {
+		match *self {
+			Repr::Rc(ref rc) => rc,
+			Repr::Static(s) => s,
+		}
+	}
+}
+
+#[derive(Clone)]
+pub struct RcStr(Repr);
 
 impl RcStr {
 	/// Create a new ref-counted string which is a copy of `value`.
@@ -39,18 +85,110 @@ impl RcStr {
 	/// assert_eq!(b, "foo");
 	/// ```
 	pub fn new<S: Into<String>>(value: S) -> RcStr {
-		RcStr(Rc::new(value.into()))
+		RcStr(Repr::Rc(Rc::new(value.into())))
+	}
+
+	/// Wrap a `&'static str` without allocating or touching a refcount.
+	///
+	/// Useful for string literals and other data that lives for the whole
+	/// program, where the usual `Rc<String>` path would only add allocation
+	/// and refcount traffic for no benefit.
+	///
+	/// # Example
+	/// ```
+	/// use rcstr::RcStr;
+	/// const FOO: RcStr = RcStr::from_static("foo");
+	/// assert_eq!(&*FOO, "foo");
+	/// assert_eq!(FOO, RcStr::new("foo"));
+	/// ```
+	pub const fn from_static(value: &'static str) -> RcStr {
+		RcStr(Repr::Static(value))
+	}
+
+	/// Wrap an existing `Rc<String>` without copying its contents.
+	pub(crate) fn from_rc(value: Rc<String>) -> RcStr {
+		RcStr(Repr::Rc(value))
+	}
+
+	/// Get mutable access to the underlying `String`, cloning it first if it
+	/// is shared with other `RcStr` handles, or if it is currently a
+	/// `&'static str`.
+	///
+	/// # Example
+	/// ```
+	/// use rcstr::RcStr;
+	/// let mut a = RcStr::new("foo");
+	/// a.to_mut().push_str("bar");
+	/// assert_eq!(&*a, "foobar");
+	/// ```
+	pub fn to_mut(&mut self) -> &mut String {
+		if let Repr::Static(s) = self.0 {
+			self.0 = Repr::Rc(Rc::new(String::from(s)));
+		}
+		match self.0 {
+			Repr::Rc(ref mut rc) => Rc::make_mut(rc),
+			Repr::Static(_) => unreachable!(),
+		}
+	}
+
+	/// Append `s` to the string, cloning first if the data is shared.
+	pub fn push_str(&mut self, s: &str) {
+		self.to_mut().push_str(s);
+	}
+
+	/// Append `c` to the string, cloning first if the data is shared.
+	pub fn push(&mut self, c: char) {
+		self.to_mut().push(c);
+	}
+
+	/// Convert the string to its ASCII lower case equivalent in place,
+	/// cloning first if the data is shared.
+	pub fn make_ascii_lowercase(&mut self) {
+		self.to_mut().make_ascii_lowercase();
+	}
+
+	/// Convert the string to its ASCII upper case equivalent in place,
+	/// cloning first if the data is shared.
+	pub fn make_ascii_uppercase(&mut self) {
+		self.to_mut().make_ascii_uppercase();
+	}
+
+	/// Shorten the string to `len` bytes, cloning first if the data is
+	/// shared.
+	///
+	/// # Panics
+	/// Panics if `len` does not lie on a `char` boundary.
+	pub fn truncate(&mut self, len: usize) {
+		self.to_mut().truncate(len);
 	}
 }
 
 impl Eq for RcStr {}
 
+impl PartialEq for RcStr {
+	fn eq(&self, other: &RcStr) -> bool {
+		self[..] == other[..]
+	}
+}
+
+impl PartialOrd for RcStr {
+	fn partial_cmp(&self, other: &RcStr) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
 impl Ord for RcStr {
 	fn cmp(&self, other: &RcStr) -> Ordering {
 		self[..].cmp(&other[..])
 	}
 }
 
+impl Hash for RcStr {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		self[..].hash(state)
+	}
+}
+
 impl fmt::Debug for RcStr {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
 		self[..].fmt(f)
@@ -65,19 +203,19 @@ impl fmt::Display for RcStr {
 
 impl Borrow<str> for RcStr {
 	fn borrow(&self) -> &str {
-		&self.0[..]
+		self.0.as_str()
 	}
 }
 
 impl Deref for RcStr {
 	type Target = str;
 	fn deref(&self) -> &str {
-		&self.0[..]
+		self.0.as_str()
 	}
 }
 
 impl AsRef<str> for RcStr {
 	fn as_ref(&self) -> &str {
-		&self.0[..]
+		self.0.as_str()
 	}
 }